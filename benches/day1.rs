@@ -0,0 +1,13 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+
+#[path = "../src/day1.rs"]
+mod day1;
+
+fn benchmarks(c: &mut Criterion) {
+    day1::boilerplate_bench(c);
+}
+
+criterion_group!(benches, benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,13 @@
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::Criterion;
+
+#[path = "../src/day2.rs"]
+mod day2;
+
+fn benchmarks(c: &mut Criterion) {
+    day2::boilerplate_bench(c);
+}
+
+criterion_group!(benches, benchmarks);
+criterion_main!(benches);
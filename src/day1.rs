@@ -1,52 +1,135 @@
+use std::collections::HashMap;
 use std::collections::VecDeque;
-use std::io;
-use std::io::BufRead;
-use std::io::Stdin;
 use std::str;
-use std::str::Chars;
+use std::sync::OnceLock;
 
-use anyhow::anyhow;
 use anyhow::Result;
 
-trait FromWord<T> {
-    fn from_word(word: &str) -> Option<T>;
+use aoc_2023::run;
+use aoc_2023::Solution;
+
+/// A trie-backed Aho-Corasick automaton over a fixed set of `(pattern,
+/// value)` pairs, letting every pattern be matched in a single left-to-right
+/// pass instead of re-scanning from every suffix.
+struct AhoCorasick {
+    goto: Vec<HashMap<u8, usize>>,
+    fail: Vec<usize>,
+    output: Vec<Vec<u32>>,
 }
 
-#[derive(Debug)]
-struct Calibration {
-    raw: String,
+impl AhoCorasick {
+    const ROOT: usize = 0;
+
+    fn new(patterns: &[(&str, u32)]) -> Self {
+        let mut goto = vec![HashMap::new()];
+        let mut output = vec![Vec::new()];
+
+        for &(pattern, value) in patterns {
+            let mut node = Self::ROOT;
+
+            for byte in pattern.bytes() {
+                node = match goto[node].get(&byte) {
+                    Some(&next) => next,
+                    None => {
+                        goto.push(HashMap::new());
+                        output.push(Vec::new());
+
+                        let next = goto.len() - 1;
+                        goto[node].insert(byte, next);
+                        next
+                    }
+                };
+            }
+
+            output[node].push(value);
+        }
+
+        let mut fail = vec![Self::ROOT; goto.len()];
+        let mut queue = VecDeque::new();
+
+        for &node in goto[Self::ROOT].values() {
+            queue.push_back(node);
+        }
+
+        while let Some(node) = queue.pop_front() {
+            let children = goto[node].clone();
+
+            for (byte, child) in children {
+                queue.push_back(child);
+
+                let mut fallback = fail[node];
+                while fallback != Self::ROOT && !goto[fallback].contains_key(&byte) {
+                    fallback = fail[fallback];
+                }
+
+                fail[child] = goto[fallback]
+                    .get(&byte)
+                    .copied()
+                    .filter(|&next| next != child)
+                    .unwrap_or(Self::ROOT);
+
+                let inherited = output[fail[child]].clone();
+                output[child].extend(inherited);
+            }
+        }
+
+        Self { goto, fail, output }
+    }
+
+    /// Scans `text` once, emitting every pattern's value in the order its
+    /// match ends, including overlapping matches like `"eight"` and `"two"`
+    /// both ending inside `"eightwothree"`.
+    fn scan(&self, text: &str) -> Vec<u32> {
+        let mut node = Self::ROOT;
+        let mut matches = Vec::new();
+
+        for byte in text.bytes() {
+            while node != Self::ROOT && !self.goto[node].contains_key(&byte) {
+                node = self.fail[node];
+            }
+
+            node = self.goto[node].get(&byte).copied().unwrap_or(Self::ROOT);
+            matches.extend(self.output[node].iter().copied());
+        }
+
+        matches
+    }
 }
 
-#[derive(Debug)]
-struct Trebuchet {
-    calibrations: Vec<Calibration>,
+fn digit_automaton() -> &'static AhoCorasick {
+    static AUTOMATON: OnceLock<AhoCorasick> = OnceLock::new();
+
+    AUTOMATON.get_or_init(|| {
+        AhoCorasick::new(&[
+            ("1", 1),
+            ("2", 2),
+            ("3", 3),
+            ("4", 4),
+            ("5", 5),
+            ("6", 6),
+            ("7", 7),
+            ("8", 8),
+            ("9", 9),
+            ("one", 1),
+            ("two", 2),
+            ("three", 3),
+            ("four", 4),
+            ("five", 5),
+            ("six", 6),
+            ("seven", 7),
+            ("eight", 8),
+            ("nine", 9),
+        ])
+    })
 }
 
-impl FromWord<Self> for u32 {
-    fn from_word(word: &str) -> Option<Self> {
-        [
-            "1", "2", "3", "4", "5", "6", "7", "8", "9", "one", "two", "three", "four", "five",
-            "six", "seven", "eight", "nine",
-        ]
-        .into_iter()
-        .flat_map(|name| word.find(name).map(|idx| (name, idx)))
-        .min_by(|(_, idx_a), (_, idx_b)| idx_a.cmp(idx_b))
-        .map(|(digit, _)| digit)
-        .and_then(|digit| match digit {
-            "1" | "one" => Some(1),
-            "2" | "two" => Some(2),
-            "3" | "three" => Some(3),
-            "4" | "four" => Some(4),
-            "5" | "five" => Some(5),
-            "6" | "six" => Some(6),
-            "7" | "seven" => Some(7),
-            "8" | "eight" => Some(8),
-            "9" | "nine" => Some(9),
-            _ => None,
-        })
-    }
+#[derive(Debug)]
+struct Calibration {
+    raw: String,
 }
 
+struct Trebuchet;
+
 impl Calibration {
     const RADIX: u32 = 10;
 
@@ -55,75 +138,64 @@ impl Calibration {
     }
 
     fn value(&self) -> Option<u32> {
-        let nums = self
+        let mut nums = self
             .raw
             .chars()
             .filter_map(|char| char.to_digit(Self::RADIX));
 
         let first = nums.clone().next()?;
-        let last = nums.rev().next()?;
+        let last = nums.next_back()?;
 
         Some((Self::RADIX * first) + last)
     }
 
     fn value2(&self) -> Option<u32> {
-        const WINDOW_LEN: usize = 5;
+        let digits = digit_automaton().scan(&self.raw);
 
-        let nums = self
-            .raw
-            .char_indices()
-            .map(|(idx, _)| &self.raw[idx..])
-            .filter_map(u32::from_word);
-
-        let first = nums.clone().next()?;
-        let last = nums.rev().next()?;
+        let first = *digits.first()?;
+        let last = *digits.last()?;
 
         Some((Self::RADIX * first) + last)
     }
 }
 
-impl Trebuchet {
-    fn from_stdin(stdin: Stdin) -> Result<Self> {
-        let calibrations = stdin
-            .lock()
-            .lines()
-            .take_while(|line| {
-                line.as_deref()
-                    .map(|line| !line.is_empty())
-                    .unwrap_or_default()
-            })
-            .map(|line| line.map_err(|err| anyhow!(err)))
-            .collect::<Result<Vec<_>>>()?
-            .into_iter()
-            .map(Calibration::from_raw)
-            .collect();
-
-        Ok(Self { calibrations })
+fn part_one(calibrations: &[Calibration]) -> Option<u32> {
+    calibrations.iter().map(Calibration::value).sum()
+}
+
+fn part_two(calibrations: &[Calibration]) -> Option<u32> {
+    calibrations.iter().map(Calibration::value2).sum()
+}
+
+impl Solution for Trebuchet {
+    type Input = Vec<Calibration>;
+
+    fn parse(lines: &[String]) -> Result<Self::Input> {
+        Ok(lines.iter().cloned().map(Calibration::from_raw).collect())
     }
 
-    fn value(&self) -> Option<u32> {
-        self.calibrations.iter().map(Calibration::value).sum()
+    fn part_one(input: &Self::Input) -> String {
+        part_one(input).unwrap_or_default().to_string()
     }
 
-    fn value2(&self) -> Option<u32> {
-        self.calibrations.iter().map(Calibration::value2).sum()
+    fn part_two(input: &Self::Input) -> String {
+        part_two(input).unwrap_or_default().to_string()
     }
 }
 
+/// Dead when this file is pulled in as `mod day1` by `benches/day1.rs`,
+/// which only reaches [`boilerplate_bench`] and never calls this.
+#[allow(dead_code)]
 fn main() -> Result<()> {
-    let stdin = io::stdin();
-    let trebuchet = Trebuchet::from_stdin(stdin)?;
-
-    println!("{trebuchet:#?}");
-
-    println!("Part one: {:?}", trebuchet.value());
-    println!("Part two: {:?}", trebuchet.value2());
-
-    Ok(())
+    run::<Trebuchet>()
 }
 
 #[cfg(test)]
 mod tests {
+    // Unused in that same bench build: `cargo bench` compiles this module
+    // with `cfg(test)` set but without wiring `#[test]` fns into a harness,
+    // so nothing here ends up calling through the glob import.
+    #[allow(unused_imports)]
     use super::*;
 
     #[test]
@@ -222,56 +294,28 @@ mod tests {
         );
     }
 
-    #[test]
-    fn trebuchet_value() {
-        let trebuchet = Trebuchet {
-            calibrations: vec![
-                Calibration {
-                    raw: "1abc2".to_string(),
-                },
-                Calibration {
-                    raw: "pqr3stu8vwx".to_string(),
-                },
-                Calibration {
-                    raw: "a1b2c3d4e5f".to_string(),
-                },
-                Calibration {
-                    raw: "treb7uchet".to_string(),
-                },
-            ],
-        };
-
-        assert_eq!(trebuchet.value().unwrap_or_default(), 142);
-    }
+}
 
-    #[test]
-    fn trebuchet_value2() {
-        let trebuchet = Trebuchet {
-            calibrations: vec![
-                Calibration {
-                    raw: "two1nine".to_string(),
-                },
-                Calibration {
-                    raw: "eightwothree".to_string(),
-                },
-                Calibration {
-                    raw: "abcone2threexyz".to_string(),
-                },
-                Calibration {
-                    raw: "xtwone3four".to_string(),
-                },
-                Calibration {
-                    raw: "4nineeightseven2".to_string(),
-                },
-                Calibration {
-                    raw: "zoneight234".to_string(),
-                },
-                Calibration {
-                    raw: "7pqrstsixteen".to_string(),
-                },
-            ],
-        };
-
-        assert_eq!(trebuchet.value2().unwrap_or_default(), 281);
-    }
+// Part two's spelled-out digits (e.g. "eight" in "eightwothree") have no
+// literal digit characters, so part one's sample has to be its own input
+// rather than sharing part two's: checking part one against the latter
+// doesn't verify anything, it just happens to fall out of the summed
+// Option<u32>s short-circuiting to None on the first digit-less line.
+const PART_ONE_INPUT: &str = "1abc2\n\
+pqr3stu8vwx\n\
+a1b2c3d4e5f\n\
+treb7uchet";
+
+const PART_TWO_INPUT: &str = "two1nine\n\
+eightwothree\n\
+abcone2threexyz\n\
+xtwone3four\n\
+4nineeightseven2\n\
+zoneight234\n\
+7pqrstsixteen";
+
+aoc_2023::boilerplate! {
+    Trebuchet,
+    part_one => (PART_ONE_INPUT, 142),
+    part_two => (PART_TWO_INPUT, 281),
 }
@@ -0,0 +1,166 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::io::BufRead;
+
+use anyhow::anyhow;
+use anyhow::Result;
+
+/// A single day's puzzle: how to parse its input and how to compute each part.
+pub trait Solution {
+    type Input;
+
+    fn parse(lines: &[String]) -> Result<Self::Input>;
+    fn part_one(input: &Self::Input) -> String;
+    fn part_two(input: &Self::Input) -> String;
+}
+
+/// Reads input (stdin, or a path given as the first argument), parses it, and
+/// prints both parts for `S`.
+pub fn run<S: Solution>() -> Result<()> {
+    let lines = read_lines()?;
+    let input = S::parse(&lines)?;
+
+    println!("Part one: {}", S::part_one(&input));
+    println!("Part two: {}", S::part_two(&input));
+
+    Ok(())
+}
+
+/// Re-exported so `boilerplate!` can reach criterion from a day's module
+/// without every day needing its own `use criterion::...`.
+pub use criterion;
+
+/// Generates the standard `#[test]` cases, plus a `boilerplate_bench`
+/// function wired up by `benches/*.rs` via `criterion_group!`/
+/// `criterion_main!`, for a day's [`Solution`] impl, so a day only has to
+/// declare its sample input(s) and expected answers instead of
+/// hand-writing the harness around them:
+///
+/// ```ignore
+/// boilerplate! {
+///     Game,
+///     TEST_INPUT,
+///     part_one => 8,
+///     part_two => 2286,
+/// }
+/// ```
+///
+/// When the two parts' samples differ (e.g. part two extends part one's
+/// rules over input part one's solution can't make sense of), give each part
+/// its own input instead of sharing one:
+///
+/// ```ignore
+/// boilerplate! {
+///     Trebuchet,
+///     part_one => (PART_ONE_INPUT, 142),
+///     part_two => (PART_TWO_INPUT, 281),
+/// }
+/// ```
+#[macro_export]
+macro_rules! boilerplate {
+    ($solution:ty, $input:expr, part_one => $part_one:expr, part_two => $part_two:expr $(,)?) => {
+        $crate::boilerplate! {
+            $solution,
+            part_one => ($input, $part_one),
+            part_two => ($input, $part_two),
+        }
+    };
+    (
+        $solution:ty,
+        part_one => ($part_one_input:expr, $part_one:expr),
+        part_two => ($part_two_input:expr, $part_two:expr) $(,)?
+    ) => {
+        #[cfg(test)]
+        mod boilerplate {
+            use super::*;
+
+            fn input(raw: &str) -> <$solution as $crate::Solution>::Input {
+                let lines = raw.lines().map(str::to_string).collect::<Vec<_>>();
+
+                <$solution as $crate::Solution>::parse(&lines).unwrap()
+            }
+
+            #[test]
+            fn part_one() {
+                assert_eq!(
+                    <$solution as $crate::Solution>::part_one(&input($part_one_input)),
+                    $part_one.to_string(),
+                );
+            }
+
+            #[test]
+            fn part_two() {
+                assert_eq!(
+                    <$solution as $crate::Solution>::part_two(&input($part_two_input)),
+                    $part_two.to_string(),
+                );
+            }
+        }
+
+        /// Only called by the matching `benches/*.rs`, which reaches this
+        /// file as a module via `#[path]`.
+        #[allow(dead_code)]
+        pub(crate) fn boilerplate_bench(c: &mut $crate::criterion::Criterion) {
+            fn lines(raw: &str) -> Vec<String> {
+                raw.lines().map(str::to_string).collect()
+            }
+
+            let part_one_lines = lines($part_one_input);
+            c.bench_function(concat!(stringify!($solution), "/parse/part_one"), |b| {
+                b.iter(|| {
+                    <$solution as $crate::Solution>::parse($crate::criterion::black_box(
+                        &part_one_lines,
+                    ))
+                })
+            });
+
+            let part_one_input = <$solution as $crate::Solution>::parse(&part_one_lines).unwrap();
+            c.bench_function(concat!(stringify!($solution), "/part_one"), |b| {
+                b.iter(|| {
+                    <$solution as $crate::Solution>::part_one($crate::criterion::black_box(
+                        &part_one_input,
+                    ))
+                })
+            });
+
+            let part_two_lines = lines($part_two_input);
+            c.bench_function(concat!(stringify!($solution), "/parse/part_two"), |b| {
+                b.iter(|| {
+                    <$solution as $crate::Solution>::parse($crate::criterion::black_box(
+                        &part_two_lines,
+                    ))
+                })
+            });
+
+            let part_two_input = <$solution as $crate::Solution>::parse(&part_two_lines).unwrap();
+            c.bench_function(concat!(stringify!($solution), "/part_two"), |b| {
+                b.iter(|| {
+                    <$solution as $crate::Solution>::part_two($crate::criterion::black_box(
+                        &part_two_input,
+                    ))
+                })
+            });
+        }
+    };
+}
+
+fn read_lines() -> Result<Vec<String>> {
+    match env::args().nth(1) {
+        Some(path) => Ok(fs::read_to_string(path)?
+            .lines()
+            .take_while(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect()),
+        None => io::stdin()
+            .lock()
+            .lines()
+            .take_while(|line| {
+                line.as_deref()
+                    .map(|line| !line.is_empty())
+                    .unwrap_or_default()
+            })
+            .map(|line| line.map_err(|err| anyhow!(err)))
+            .collect(),
+    }
+}